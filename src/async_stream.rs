@@ -0,0 +1,112 @@
+//! An async [`Stream`] adapter over [`LocalArrowArrayStreamReader`], available behind the `async`
+//! feature.
+//!
+//! Every `next()` call on [LocalArrowArrayStreamReader] enters GraalVM through a blocking FFI
+//! call, which would stall an async runtime if driven directly from a task. [AsyncArrowStream]
+//! instead moves each call onto a blocking executor via [`tokio::task::spawn_blocking`].
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use arrow::datatypes::SchemaRef;
+use arrow::error::{ArrowError, Result};
+use arrow::{array::RecordBatch, array::RecordBatchReader};
+use futures::Stream;
+use tokio::task::JoinHandle;
+
+use crate::LocalArrowArrayStreamReader;
+
+type NextResult = (LocalArrowArrayStreamReader, Option<Result<RecordBatch>>);
+
+enum State {
+    // holds the reader between polls; taken for the duration of a blocking call so at most
+    // one batch is ever in flight at a time
+    Idle(Option<LocalArrowArrayStreamReader>),
+    Polling(JoinHandle<NextResult>),
+}
+
+/// A [`Stream`] of [`RecordBatch`]es that drives a [`LocalArrowArrayStreamReader`] on a blocking
+/// executor instead of the calling task.
+///
+/// Holds the same `Arc<GraalIsolate>` clone as the reader it wraps, so the isolate stays alive for
+/// as long as the stream is being polled.
+pub struct AsyncArrowStream {
+    schema: SchemaRef,
+    state: State,
+}
+
+impl AsyncArrowStream {
+    pub(crate) fn new(reader: LocalArrowArrayStreamReader) -> Self {
+        Self {
+            schema: reader.schema(),
+            state: State::Idle(Some(reader)),
+        }
+    }
+
+    /// The schema of the batches this stream yields.
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for AsyncArrowStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match &mut this.state {
+                State::Idle(reader) => {
+                    let Some(mut reader) = reader.take() else {
+                        // the blocking task that owned the reader panicked or was cancelled;
+                        // there is nothing left to poll.
+                        return Poll::Ready(None);
+                    };
+                    let handle = tokio::task::spawn_blocking(move || {
+                        let item = reader.next();
+                        (reader, item)
+                    });
+                    this.state = State::Polling(handle);
+                }
+                State::Polling(handle) => {
+                    return match Pin::new(handle).poll(cx) {
+                        Poll::Ready(Ok((reader, item))) => {
+                            this.state = State::Idle(Some(reader));
+                            Poll::Ready(item)
+                        }
+                        Poll::Ready(Err(join_error)) => {
+                            this.state = State::Idle(None);
+                            Poll::Ready(Some(Err(ArrowError::ExternalError(Box::new(join_error)))))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod test {
+    use futures::StreamExt;
+
+    use crate::GraalArrowStreamer;
+
+    #[tokio::test]
+    async fn should_call_stream_async() -> arrow::error::Result<()> {
+        let streamer = GraalArrowStreamer::try_new_from_name_and_path("gas", "./target/java")?;
+        let mut stream = streamer.create_reader_async("path")?;
+
+        assert!(stream.next().await.is_some());
+        assert!(stream.next().await.is_some());
+        assert!(stream.next().await.is_some());
+        assert!(stream.next().await.is_some());
+
+        let schema = stream.schema();
+
+        assert!(schema.field_with_name("age").is_ok());
+
+        Ok(())
+    }
+}
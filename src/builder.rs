@@ -0,0 +1,215 @@
+//! A richer, panic-free way to locate and load the native library backing a [GraalArrowStreamer].
+//!
+//! The `try_new_from_*` constructors on [GraalArrowStreamer] cover the common case of a single
+//! platform-named library on `LD_LIBRARY_PATH`. [GraalArrowStreamerBuilder] is for the less common
+//! one: probing several candidate filenames across several search directories, and getting back a
+//! single error naming every required symbol the native image is missing, rather than failing on
+//! the first one.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::error::{ArrowError, Result};
+use libloading::Library;
+
+use crate::GraalArrowStreamer;
+
+/// Symbols every native image must export for [GraalArrowStreamer] to function. Logging symbols
+/// and `gas_writer_stream` are intentionally not part of this list - they are optional, since not
+/// every native image implements the write direction.
+const REQUIRED_SYMBOLS: &[&str] = &[
+    "graal_create_isolate",
+    "graal_tear_down_isolate",
+    "graal_detach_thread",
+    "graal_attach_thread",
+    "gas_reader_stream",
+    "gas_last_error",
+];
+
+/// Builds a [GraalArrowStreamer] by resolving a native library across platform-specific candidate
+/// filenames and search directories, rather than panicking or failing on the first miss.
+#[derive(Debug, Default)]
+pub struct GraalArrowStreamerBuilder {
+    library_name: Option<OsString>,
+    library_file: Option<PathBuf>,
+    search_dirs: Vec<PathBuf>,
+    candidate_filenames: Vec<OsString>,
+}
+
+impl GraalArrowStreamerBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the library name without a platform-specific prefix/suffix, e.g. `gas` for
+    /// `libgas.so`. The platform-derived filename is always tried first, ahead of any filenames
+    /// added with [Self::candidate_filename].
+    pub fn library_name(mut self, name: impl AsRef<OsStr>) -> Self {
+        self.library_name = Some(name.as_ref().to_os_string());
+        self
+    }
+
+    /// Sets an explicit path to the library file, bypassing name-based resolution entirely.
+    pub fn library_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.library_file = Some(file.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds a directory to search for the library, ahead of the platform's own default
+    /// resolution (`LD_LIBRARY_PATH`/rpath). Directories are tried in the order added; the bare
+    /// filenames (platform-derived first, then any candidates) are still tried last as a fallback.
+    pub fn search_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.search_dirs.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds an extra candidate filename to try in each search directory and as a bare filename, in addition to the
+    /// platform-derived one from [Self::library_name]. Tried in the order added.
+    pub fn candidate_filename(mut self, filename: impl AsRef<OsStr>) -> Self {
+        self.candidate_filenames
+            .push(filename.as_ref().to_os_string());
+        self
+    }
+
+    /// Resolves and loads the library, verifies every required symbol is present, and builds the
+    /// [GraalArrowStreamer].
+    ///
+    /// # Returns
+    /// * `Result<GraalArrowStreamer>` - The streamer, or an error naming every candidate path
+    ///   tried and why it failed to load, or every required symbol the library doesn't export.
+    pub fn build(self) -> Result<GraalArrowStreamer> {
+        let lib = self.load_library()?;
+
+        verify_required_symbols(&lib)?;
+
+        GraalArrowStreamer::try_new(Arc::new(lib))
+    }
+
+    fn load_library(&self) -> Result<Library> {
+        if let Some(file) = &self.library_file {
+            return load_candidate(file);
+        }
+
+        let Some(name) = &self.library_name else {
+            return Err(ArrowError::CDataInterface(
+                "GraalArrowStreamerBuilder requires either library_name or library_file".into(),
+            ));
+        };
+
+        let mut filenames = vec![libloading::library_filename(name)];
+        filenames.extend(self.candidate_filenames.iter().cloned());
+
+        // `search_dir` prepends extra directories to try, it doesn't replace the default
+        // resolution of a bare filename through the OS's own `LD_LIBRARY_PATH`/rpath lookup -
+        // so the directory-joined candidates always come first, with the bare filenames tried
+        // last as a fallback.
+        let mut candidates: Vec<PathBuf> = self
+            .search_dirs
+            .iter()
+            .flat_map(|dir| {
+                filenames.iter().map(|filename| {
+                    let mut candidate = dir.clone();
+                    candidate.push(filename);
+                    candidate
+                })
+            })
+            .collect();
+        candidates.extend(filenames.into_iter().map(PathBuf::from));
+
+        if candidates.is_empty() {
+            return Err(ArrowError::CDataInterface(format!(
+                "no candidate filenames to try for library `{}`",
+                name.to_string_lossy()
+            )));
+        }
+
+        let mut failures = Vec::new();
+        for candidate in &candidates {
+            match load_candidate(candidate) {
+                Ok(lib) => return Ok(lib),
+                Err(e) => failures.push(format!("{}: {e}", candidate.display())),
+            }
+        }
+
+        Err(ArrowError::CDataInterface(format!(
+            "could not load library `{}` from any candidate path: {}",
+            name.to_string_lossy(),
+            failures.join("; ")
+        )))
+    }
+}
+
+fn load_candidate(path: &Path) -> Result<Library> {
+    unsafe { Library::new(path).map_err(|e| ArrowError::ExternalError(Box::new(e))) }
+}
+
+/// Collects every symbol in [REQUIRED_SYMBOLS] that `lib` doesn't export into a single error,
+/// instead of failing as soon as the first one is missing.
+fn verify_required_symbols(lib: &Library) -> Result<()> {
+    let missing: Vec<&str> = REQUIRED_SYMBOLS
+        .iter()
+        .filter(|name| unsafe { lib.get::<*const ()>(name.as_bytes()) }.is_err())
+        .copied()
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ArrowError::CDataInterface(format!(
+            "native library is missing required symbols: {}",
+            missing.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GraalArrowStreamerBuilder;
+
+    #[test]
+    fn should_require_library_name_or_file() {
+        let err = GraalArrowStreamerBuilder::new().build().unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("requires either library_name or library_file")
+        );
+    }
+
+    #[test]
+    fn should_aggregate_errors_across_search_dirs() {
+        let err = GraalArrowStreamerBuilder::new()
+            .library_name("definitely-missing")
+            .search_dir("./does/not/exist/a")
+            .search_dir("./does/not/exist/b")
+            .build()
+            .unwrap_err();
+
+        let message = err.to_string();
+
+        // one combined error mentioning every candidate path tried, not just the first failure
+        assert!(message.contains("does/not/exist/a"));
+        assert!(message.contains("does/not/exist/b"));
+    }
+
+    #[test]
+    fn should_try_bare_filename_as_fallback_after_search_dirs() {
+        // `search_dir` prepends extra directories, it doesn't replace the platform's own default
+        // resolution of the bare, platform-derived filename - so that candidate must still show
+        // up in the failure report even once a search_dir has been added.
+        let err = GraalArrowStreamerBuilder::new()
+            .library_name("definitely-missing")
+            .search_dir("./does/not/exist")
+            .build()
+            .unwrap_err();
+
+        let message = err.to_string();
+        let bare_filename = libloading::library_filename("definitely-missing");
+
+        assert!(message.contains("does/not/exist"));
+        // the bare candidate's own entry follows the `; ` separator, unlike the dir-joined one
+        assert!(message.contains(&format!("; {}: ", bare_filename.to_string_lossy())));
+    }
+}
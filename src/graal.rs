@@ -19,9 +19,43 @@ pub(crate) type FnCreateReader = unsafe extern "C" fn(
     arg3: ::std::os::raw::c_long,
 ) -> ::std::os::raw::c_int;
 
+/// Symmetric to [FnCreateReader]: drains a Rust-owned `FFI_ArrowArrayStream` (`arg3`, passed by
+/// address) into the sink named by `arg2`.
+pub(crate) type FnCreateWriter = unsafe extern "C" fn(
+    arg1: *mut graal_isolatethread_t,
+    arg2: *mut ::std::os::raw::c_char,
+    arg3: ::std::os::raw::c_long,
+) -> ::std::os::raw::c_int;
+
 ///
 pub(crate) type FnLastError = unsafe extern "C" fn(
     arg1: *mut graal_isolatethread_t,
     arg2: *mut ::std::os::raw::c_char,
     arg3: ::std::os::raw::c_int,
 ) -> ::std::os::raw::c_int;
+
+/// Signature of the crate-provided trampoline handed to `gas_set_log_callback`.
+pub(crate) type LogCallbackFn = extern "C" fn(
+    level: ::std::os::raw::c_int,
+    msg: *const ::std::os::raw::c_char,
+    len: usize,
+);
+
+/// All of the following log symbols are optional - a native image that doesn't export them is not
+/// an error, logging is simply unavailable.
+///
+pub(crate) type FnLogConsoleOn =
+    unsafe extern "C" fn(*mut graal_isolatethread_t) -> ::std::os::raw::c_int;
+///
+pub(crate) type FnLogConsoleOff =
+    unsafe extern "C" fn(*mut graal_isolatethread_t) -> ::std::os::raw::c_int;
+///
+pub(crate) type FnSetLogLevel = unsafe extern "C" fn(
+    arg1: *mut graal_isolatethread_t,
+    level: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int;
+///
+pub(crate) type FnSetLogCallback = unsafe extern "C" fn(
+    arg1: *mut graal_isolatethread_t,
+    callback: LogCallbackFn,
+) -> ::std::os::raw::c_int;
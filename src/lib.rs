@@ -4,16 +4,25 @@
 //! the Arrow C Data Interface. The main components are:
 //!
 //! - [GraalArrowStreamer]: Main interface for creating and managing GraalVM isolates and Arrow streams
-//! - [LocalArrowArrayStreamReader]: A record batch reader that is tied to the lifetime of its parent isolate
+//! - [LocalArrowArrayStreamReader]: A record batch reader backed by a shared reference to the isolate
+//! - [GraalArrowStreamerBuilder]: Resolves and loads the native library across candidate filenames
+//!   and search directories, verifying every required symbol up front
 //!
 //! # Safety
 //!
 //! This struct handles unsafe FFI calls to GraalVM native methods and manages memory/resource cleanup.
 //! It is marked as `Send` and `Sync` since the underlying GraalVM isolate can be safely shared between threads.
 //!
-//! [GraalArrowStreamer] has to outlive any batches created by any of created [LocalArrowArrayStreamReader]
-//! otherwise `SIGSEGV` may occur, as dropping of the batches will try to call isolate which produced it to
-//! release memory.
+//! [GraalArrowStreamer] is backed by a reference-counted isolate handle, so cloning it is cheap and the
+//! isolate is only torn down once every [GraalArrowStreamer] and every [LocalArrowArrayStreamReader] created
+//! from it have been dropped. This removes the old requirement that the streamer strictly outlive its
+//! readers. A batch produced by [LocalArrowArrayStreamReader] still carries an Arrow C Data release callback
+//! into the isolate but holds no `Arc` handle of its own, so it must not be kept alive longer than the reader
+//! that produced it.
+//!
+//! When a batch must genuinely outlive the isolate it was read from - for example to hand it to a
+//! long-lived cache after the isolate has been torn down - use [GraalArrowStreamer::create_reader_owned]
+//! instead, which deep-copies every batch into Rust-owned memory before returning it.
 //!
 //! # Example
 //!
@@ -40,8 +49,9 @@
 //! - Native library handle
 //! - Thread attachments
 //!
-//! The isolate is automatically torn down when the [GraalArrowStreamer] is dropped.
-//! Readers created by this struct must not outlive the [GraalArrowStreamer] instance.
+//! The isolate is torn down once the last clone of [GraalArrowStreamer] and the last reader created
+//! from it have been dropped. Batches from [GraalArrowStreamer::create_reader_owned] hold no reference
+//! to the isolate.
 
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
@@ -50,7 +60,6 @@
 
 use std::{
     ffi::{CStr, c_char},
-    marker::PhantomData,
     mem::MaybeUninit,
     path::PathBuf,
     sync::Arc,
@@ -67,33 +76,101 @@ use libloading::{Library, Symbol};
 
 use crate::{
     bindings::{
-        gas_last_error_fn_t, gas_reader_stream_fn_t, graal_attach_thread_fn_t,
-        graal_create_isolate_fn_t, graal_detach_thread_fn_t, graal_isolate_t,
-        graal_isolatethread_t, graal_tear_down_isolate_fn_t,
+        gas_last_error_fn_t, gas_log_console_off_fn_t, gas_log_console_on_fn_t,
+        gas_reader_stream_fn_t, gas_set_log_callback_fn_t, gas_set_log_level_fn_t,
+        gas_writer_stream_fn_t, graal_attach_thread_fn_t, graal_create_isolate_fn_t,
+        graal_detach_thread_fn_t, graal_isolate_t, graal_isolatethread_t,
+        graal_tear_down_isolate_fn_t,
+    },
+    graal::{
+        FnAttachThread, FnCreateReader, FnCreateWriter, FnLastError, FnLogConsoleOff,
+        FnLogConsoleOn, FnSetLogCallback, FnSetLogLevel, FnTearDownIsolate,
     },
-    graal::{FnAttachThread, FnCreateReader, FnDetachThread, FnLastError, FnTearDownIsolate},
+    log_bridge::log_trampoline,
+    owned::deep_copy_record_batch,
 };
+#[cfg(feature = "async")]
+mod async_stream;
 mod bindings;
+mod builder;
 mod graal;
+mod log_bridge;
+mod owned;
+mod thread_attach;
+
+#[cfg(feature = "async")]
+pub use async_stream::AsyncArrowStream;
+pub use builder::GraalArrowStreamerBuilder;
+pub use log_bridge::LogLevel;
 
+/// The GraalVM isolate and the function pointers needed to drive it.
+///
+/// This is the reference-counted core behind [GraalArrowStreamer]: every clone of the streamer, every
+/// [LocalArrowArrayStreamReader] and every [OwnedArrowArrayStreamReader] holds an `Arc` to one of these, so `graal_tear_down_isolate` only runs once the last of them is dropped.
+///
+/// Thread attachment is cached per-thread (see [thread_attach]), so repeated calls from the same
+/// thread reuse the same `graal_isolatethread_t` instead of attaching and detaching every time.
 #[derive(Debug)]
-pub struct GraalArrowStreamer {
+struct GraalIsolate {
     // Isolate needs to outlive any interaction with
     // GraalVM, that includes releasing batches
     // produced by the graal stream reader
     ptr_isolate: *mut graal_isolate_t,
+    // shared registry of every thread currently attached to this isolate, reachable from here so
+    // `Drop` can detach all of them up front instead of relying on each thread's own eventual exit
+    attachments: Arc<thread_attach::IsolateAttachments>,
     f_tear_down_isolate: FnTearDownIsolate,
-    f_detach_thread: FnDetachThread,
     f_attach_thread: FnAttachThread,
     f_create_reader: FnCreateReader,
+    // optional: older native images only implement the read direction
+    f_create_writer: Option<FnCreateWriter>,
     f_last_error: FnLastError,
+    // all optional: a native image that doesn't export them simply has no logging support
+    f_log_console_on: Option<FnLogConsoleOn>,
+    f_log_console_off: Option<FnLogConsoleOff>,
+    f_set_log_level: Option<FnSetLogLevel>,
     // library needs to outlive others
     #[allow(dead_code)]
     lib: Arc<Library>,
 }
 
-unsafe impl Send for GraalArrowStreamer {}
-unsafe impl Sync for GraalArrowStreamer {}
+unsafe impl Send for GraalIsolate {}
+unsafe impl Sync for GraalIsolate {}
+
+/// # Safety
+///
+/// Note about safety, this drop force-detaches every other thread
+/// still attached to the isolate before tearing it down
+impl Drop for GraalIsolate {
+    fn drop(&mut self) {
+        unsafe {
+            // reuse this thread's own cached attachment if it has one - `graal_tear_down_isolate`
+            // detaches the thread it is given, so take the entry out of the cache rather than
+            // leaving it there to be detached again when the guard itself is dropped
+            let ptr_thread = thread_attach::take_cached_thread(&self.attachments).or_else(|| {
+                let mut ptr_thread: *mut graal_isolatethread_t =
+                    std::mem::MaybeUninit::zeroed().assume_init();
+
+                ((self.f_attach_thread)(self.ptr_isolate, &mut ptr_thread) == 0)
+                    .then_some(ptr_thread)
+            });
+
+            // force-detach every *other* thread still attached to this isolate - otherwise
+            // `graal_tear_down_isolate` blocks until they detach themselves, which may be never
+            // for a long-lived worker thread (e.g. a tokio blocking-pool thread)
+            self.attachments.detach_all();
+
+            if let Some(ptr_thread) = ptr_thread {
+                (self.f_tear_down_isolate)(ptr_thread);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GraalArrowStreamer {
+    inner: Arc<GraalIsolate>,
+}
 
 impl GraalArrowStreamer {
     /// Creates a new instance using a library name.
@@ -211,6 +288,13 @@ impl GraalArrowStreamer {
                 ArrowError::CDataInterface("can't find gas_reader_stream method".into())
             })?;
 
+            // optional: older native images only implement the read direction, and not every
+            // caller uses `create_writer`, so its absence is not a construction error
+            let f_gas_writer_stream: Option<FnCreateWriter> = lib
+                .get::<gas_writer_stream_fn_t>(b"gas_writer_stream")
+                .ok()
+                .and_then(|s| *s);
+
             let f_gas_last_error: Symbol<gas_last_error_fn_t> = lib
                 .get(b"gas_last_error")
                 .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
@@ -219,6 +303,28 @@ impl GraalArrowStreamer {
                 ArrowError::CDataInterface("can't find gas_last_error method".into())
             })?;
 
+            // logging symbols are optional - a native image that doesn't export them simply has
+            // no logging support, which is not an error
+            let f_gas_log_console_on: Option<FnLogConsoleOn> = lib
+                .get::<gas_log_console_on_fn_t>(b"gas_log_console_on")
+                .ok()
+                .and_then(|s| *s);
+
+            let f_gas_log_console_off: Option<FnLogConsoleOff> = lib
+                .get::<gas_log_console_off_fn_t>(b"gas_log_console_off")
+                .ok()
+                .and_then(|s| *s);
+
+            let f_gas_set_log_level: Option<FnSetLogLevel> = lib
+                .get::<gas_set_log_level_fn_t>(b"gas_set_log_level")
+                .ok()
+                .and_then(|s| *s);
+
+            let f_gas_set_log_callback: Option<FnSetLogCallback> = lib
+                .get::<gas_set_log_callback_fn_t>(b"gas_set_log_callback")
+                .ok()
+                .and_then(|s| *s);
+
             //
             //
             //
@@ -236,37 +342,185 @@ impl GraalArrowStreamer {
                 ));
             }
 
+            // register the crate's trampoline so native log lines surface through `tracing`
+            // instead of being lost to stdout - best-effort, the symbol may not exist
+            if let Some(f_gas_set_log_callback) = f_gas_set_log_callback {
+                f_gas_set_log_callback(ptr_thread, log_trampoline);
+            }
+
             // we're detaching thread as its only needed
             // when we interact with isolate
             f_graal_detach_thread(ptr_thread);
 
             Ok(Self {
-                lib,
-                ptr_isolate,
-                f_tear_down_isolate: f_graal_tear_down_isolate,
-                f_detach_thread: f_graal_detach_thread,
-                f_attach_thread: f_graal_attach_thread,
-                f_create_reader: f_gas_reader_stream,
-                f_last_error: f_gas_last_error,
+                inner: Arc::new(GraalIsolate {
+                    lib,
+                    ptr_isolate,
+                    attachments: thread_attach::IsolateAttachments::new(f_graal_detach_thread),
+                    f_tear_down_isolate: f_graal_tear_down_isolate,
+                    f_attach_thread: f_graal_attach_thread,
+                    f_create_reader: f_gas_reader_stream,
+                    f_create_writer: f_gas_writer_stream,
+                    f_last_error: f_gas_last_error,
+                    f_log_console_on: f_gas_log_console_on,
+                    f_log_console_off: f_gas_log_console_off,
+                    f_set_log_level: f_gas_set_log_level,
+                }),
             })
         }
     }
 
     /// Creates an Arrow array stream reader for the specified path.
     ///
+    /// The returned reader holds a clone of the streamer's isolate handle, so it may safely be
+    /// kept around after every [GraalArrowStreamer] clone that produced it has been dropped - the
+    /// isolate is only torn down once the last reference (streamer or reader) goes
+    /// away. Batches yielded by the reader still carry a release callback into the isolate, so
+    /// they must not be kept alive longer than the reader itself; use
+    /// [GraalArrowStreamer::create_reader_owned] if that is required.
+    ///
     /// # Arguments
     /// * `path` - The path to the data source
     ///
     /// # Returns
     /// * `Result<LocalArrowArrayStreamReader>` - A reader for the Arrow array stream or an error
 
-    pub fn create_reader<'local>(
-        &'local self,
-        path: &str,
-    ) -> Result<LocalArrowArrayStreamReader<'local>> {
-        unsafe {
-            let ptr_thread: *mut graal_isolatethread_t = self.attach_tread()?;
-            let c_str_path = std::ffi::CString::new(path).unwrap();
+    pub fn create_reader(&self, path: &str) -> Result<LocalArrowArrayStreamReader> {
+        let stream_reader = self.inner.open_stream(path)?;
+
+        Ok(LocalArrowArrayStreamReader {
+            inner: stream_reader,
+            isolate: Arc::clone(&self.inner),
+        })
+    }
+
+    /// Creates a reader whose batches are immediately deep-copied into Rust-owned memory.
+    ///
+    /// Every column of every batch is copied out of the foreign buffers returned by GraalVM as
+    /// soon as it is received, and the foreign `ArrowArray` is released right away. The resulting
+    /// batches hold no release callback into the isolate, so they may safely outlive both this
+    /// reader and the [GraalArrowStreamer] it was created from - at the cost of one extra copy per
+    /// batch.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the data source
+    ///
+    /// # Returns
+    /// * `Result<OwnedArrowArrayStreamReader>` - A reader yielding isolate-independent batches or an error
+    pub fn create_reader_owned(&self, path: &str) -> Result<OwnedArrowArrayStreamReader> {
+        let stream_reader = self.inner.open_stream(path)?;
+
+        Ok(OwnedArrowArrayStreamReader {
+            inner: stream_reader,
+            isolate: Arc::clone(&self.inner),
+        })
+    }
+
+    /// Creates an async [`AsyncArrowStream`] for the specified path.
+    ///
+    /// Each batch is produced by moving the underlying blocking [LocalArrowArrayStreamReader::next]
+    /// call onto a blocking executor (via [`tokio::task::spawn_blocking`]), so polling this stream
+    /// does not block the calling task. The stream owns the same isolate handle as any other
+    /// reader created from this streamer, so the isolate stays alive for as long as it is polled.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the data source
+    ///
+    /// # Returns
+    /// * `Result<AsyncArrowStream>` - An async stream of record batches or an error
+    #[cfg(feature = "async")]
+    pub fn create_reader_async(&self, path: &str) -> Result<AsyncArrowStream> {
+        let reader = self.create_reader(path)?;
+
+        Ok(AsyncArrowStream::new(reader))
+    }
+
+    /// Pushes Arrow data produced by `reader` into GraalVM through the Arrow C stream interface.
+    ///
+    /// `reader` is wrapped in an [`FFI_ArrowArrayStream`] and its address is passed to the native
+    /// `gas_writer_stream` symbol the same way [GraalArrowStreamer::create_reader] passes the
+    /// address of the stream it imports - GraalVM then drains `reader` through the C Data
+    /// Interface into the sink named by `sink`.
+    ///
+    /// `gas_writer_stream` is optional - a native image built before the write direction existed
+    /// doesn't export it, in which case this returns an error rather than panicking or failing
+    /// construction of the streamer itself.
+    ///
+    /// # Arguments
+    /// * `sink` - The name of the destination the native library should write `reader` into
+    /// * `reader` - The Rust-side batch reader to stream into GraalVM
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or an error surfaced through `gas_last_error`, or naming the
+    ///   missing symbol if the native image doesn't implement the write direction
+    pub fn create_writer<R: RecordBatchReader + Send + 'static>(
+        &self,
+        sink: &str,
+        reader: R,
+    ) -> Result<()> {
+        self.inner.write_stream(sink, reader)
+    }
+
+    /// Turns on GraalVM's own console logging, if the native image exports `gas_log_console_on`.
+    ///
+    /// A no-op returning `Ok(())` when the symbol isn't present.
+    pub fn enable_console_log(&self) -> Result<()> {
+        let Some(f_log_console_on) = self.inner.f_log_console_on else {
+            return Ok(());
+        };
+
+        self.inner.with_attached_thread(|ptr_thread| {
+            if unsafe { f_log_console_on(ptr_thread) } == 0 {
+                Ok(())
+            } else {
+                Err(ArrowError::CDataInterface(self.inner.last_error(ptr_thread)))
+            }
+        })
+    }
+
+    /// Turns off GraalVM's own console logging, if the native image exports `gas_log_console_off`.
+    ///
+    /// A no-op returning `Ok(())` when the symbol isn't present.
+    pub fn disable_console_log(&self) -> Result<()> {
+        let Some(f_log_console_off) = self.inner.f_log_console_off else {
+            return Ok(());
+        };
+
+        self.inner.with_attached_thread(|ptr_thread| {
+            if unsafe { f_log_console_off(ptr_thread) } == 0 {
+                Ok(())
+            } else {
+                Err(ArrowError::CDataInterface(self.inner.last_error(ptr_thread)))
+            }
+        })
+    }
+
+    /// Sets the native log level, if the native image exports `gas_set_log_level`.
+    ///
+    /// A no-op returning `Ok(())` when the symbol isn't present.
+    pub fn set_log_level(&self, level: LogLevel) -> Result<()> {
+        let Some(f_set_log_level) = self.inner.f_set_log_level else {
+            return Ok(());
+        };
+
+        self.inner.with_attached_thread(|ptr_thread| {
+            if unsafe { f_set_log_level(ptr_thread, level.as_c_int()) } == 0 {
+                Ok(())
+            } else {
+                Err(ArrowError::CDataInterface(self.inner.last_error(ptr_thread)))
+            }
+        })
+    }
+}
+
+impl GraalIsolate {
+    /// Opens the Arrow C stream exported by GraalVM for `path`, using this thread's cached
+    /// isolate attachment (attaching one if it doesn't have one yet).
+    fn open_stream(&self, path: &str) -> Result<ArrowArrayStreamReader> {
+        let c_str_path = std::ffi::CString::new(path)
+            .map_err(|e| ArrowError::CDataInterface(format!("invalid path `{path}`: {e}")))?;
+
+        self.with_attached_thread(|ptr_thread| unsafe {
             let c_str_path = c_str_path.into_raw();
 
             let mut ffi_stream = FFI_ArrowArrayStream::empty();
@@ -277,18 +531,67 @@ impl GraalArrowStreamer {
             //
 
             if err_code != 0 {
-                let error = self.last_error(ptr_thread);
-                self.detach_thread(ptr_thread)?;
-                return Err(ArrowError::CDataInterface(error));
+                Err(ArrowError::CDataInterface(self.last_error(ptr_thread)))
             } else {
-                self.detach_thread(ptr_thread)?;
-                let stream_reader = ArrowArrayStreamReader::from_raw(&mut ffi_stream)?;
-                Ok(LocalArrowArrayStreamReader {
-                    inner: stream_reader,
-                    pd: PhantomData::default(),
-                })
+                ArrowArrayStreamReader::from_raw(&mut ffi_stream)
             }
-        }
+        })
+    }
+
+    /// Wraps `reader` in an `FFI_ArrowArrayStream` and hands its address to the native
+    /// `gas_writer_stream` symbol, which drains it into `sink`.
+    ///
+    /// Returns an error if the native image doesn't export `gas_writer_stream` - the symbol is
+    /// optional, so this is checked here rather than at construction time.
+    fn write_stream<R: RecordBatchReader + Send + 'static>(&self, sink: &str, reader: R) -> Result<()> {
+        let f_create_writer = self.f_create_writer.ok_or_else(|| {
+            ArrowError::CDataInterface(
+                "native library does not implement gas_writer_stream".into(),
+            )
+        })?;
+
+        let c_str_sink = std::ffi::CString::new(sink)
+            .map_err(|e| ArrowError::CDataInterface(format!("invalid sink `{sink}`: {e}")))?;
+
+        self.with_attached_thread(|ptr_thread| unsafe {
+            let c_str_sink = c_str_sink.into_raw();
+
+            let ffi_stream = FFI_ArrowArrayStream::new(Box::new(reader));
+            let stream_address = std::ptr::addr_of!(ffi_stream) as i64;
+
+            let err_code = f_create_writer(ptr_thread, c_str_sink, stream_address);
+
+            if err_code != 0 {
+                Err(ArrowError::CDataInterface(self.last_error(ptr_thread)))
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Runs `f` with the `graal_isolatethread_t` this thread has already attached to this
+    /// isolate, attaching (and caching) one first if it doesn't have one yet.
+    ///
+    /// The attachment is not detached when `f` returns - it stays cached for this thread to reuse
+    /// on the next call, and is only detached once the thread it belongs to exits or the isolate
+    /// is torn down, whichever comes first.
+    ///
+    /// # Returns
+    /// * `Result<R>` - Whatever `f` returns, or an error if attaching failed
+    pub(crate) fn with_attached_thread<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(*mut graal_isolatethread_t) -> Result<R>,
+    {
+        let ptr_thread = match thread_attach::cached_thread(&self.attachments) {
+            Some(ptr_thread) => ptr_thread,
+            None => {
+                let ptr_thread = self.attach_tread()?;
+                thread_attach::cache_thread(&self.attachments, ptr_thread);
+                ptr_thread
+            }
+        };
+
+        f(ptr_thread)
     }
 
     /// Retrieves the last error message from the GraalVM context.
@@ -316,9 +619,6 @@ impl GraalArrowStreamer {
     ///
     /// # Returns
     /// * `Result<*mut graal_isolatethread_t>` - Pointer to the attached thread or an error
-
-    // We could create handles to detach threads when they are out of scope
-    // but for this simple example it would be overkill.
     fn attach_tread(&self) -> Result<*mut graal_isolatethread_t> {
         unsafe {
             let mut ptr_thread: *mut graal_isolatethread_t =
@@ -331,33 +631,17 @@ impl GraalArrowStreamer {
             }
         }
     }
-    /// Detaches a thread from the GraalVM isolate.
-    ///
-    /// # Arguments
-    /// * `ptr_thread` - Pointer to the thread to detach
-    ///
-    /// # Returns
-    /// * `Result<()>` - Success or error status
-
-    fn detach_thread(&self, ptr_thread: *mut graal_isolatethread_t) -> Result<()> {
-        unsafe {
-            if (self.f_detach_thread)(ptr_thread) == 0 {
-                Ok(())
-            } else {
-                Err(ArrowError::CDataInterface("can't detach thread".into()))
-            }
-        }
-    }
 }
 
-// we want to prevent stream reader to outlive
-// isolate from which was created
-pub struct LocalArrowArrayStreamReader<'local> {
+// the reader keeps the isolate alive via `Arc`, but the batches it yields
+// still carry a release callback into that isolate, so they must not be
+// kept alive past the reader itself.
+pub struct LocalArrowArrayStreamReader {
     inner: ArrowArrayStreamReader,
-    pd: std::marker::PhantomData<&'local ArrowArrayStreamReader>,
+    isolate: Arc<GraalIsolate>,
 }
 
-impl<'local> Iterator for LocalArrowArrayStreamReader<'local> {
+impl Iterator for LocalArrowArrayStreamReader {
     type Item = arrow::error::Result<RecordBatch>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -365,27 +649,33 @@ impl<'local> Iterator for LocalArrowArrayStreamReader<'local> {
     }
 }
 
-impl<'local> RecordBatchReader for LocalArrowArrayStreamReader<'local> {
+impl RecordBatchReader for LocalArrowArrayStreamReader {
     fn schema(&self) -> SchemaRef {
         self.inner.schema()
     }
 }
-/// # Safety
-///
-/// Note about safety, this drop can block if there are
-/// threads attached to isolate
-impl Drop for GraalArrowStreamer {
-    fn drop(&mut self) {
-        unsafe {
-            // attaching thread to interact with graal
-            // it is needed to call `f_tear_down_isolate`
-            let mut ptr_thread: *mut graal_isolatethread_t =
-                std::mem::MaybeUninit::zeroed().assume_init();
 
-            if (self.f_attach_thread)(self.ptr_isolate, &mut ptr_thread) == 0 {
-                (self.f_tear_down_isolate)(ptr_thread);
-            }
-        }
+/// A stream reader whose batches have been deep-copied out of isolate-owned memory, see
+/// [GraalArrowStreamer::create_reader_owned].
+pub struct OwnedArrowArrayStreamReader {
+    inner: ArrowArrayStreamReader,
+    // kept alive for as long as the reader, since releasing `inner` calls
+    // into the isolate; the batches it yields do not reference it.
+    isolate: Arc<GraalIsolate>,
+}
+
+impl Iterator for OwnedArrowArrayStreamReader {
+    type Item = arrow::error::Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let _isolate = &self.isolate;
+        self.inner.next().map(|batch| deep_copy_record_batch(&batch?))
+    }
+}
+
+impl RecordBatchReader for OwnedArrowArrayStreamReader {
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
     }
 }
 
@@ -393,7 +683,7 @@ impl Drop for GraalArrowStreamer {
 mod test {
 
     use crate::GraalArrowStreamer;
-    use arrow::array::RecordBatchReader;
+    use arrow::array::{RecordBatchIterator, RecordBatchReader};
 
     #[test]
     fn should_call_stream() -> arrow::error::Result<()> {
@@ -430,25 +720,40 @@ mod test {
     }
 
     #[test]
-    #[ignore = "if streamer is dropped before last batch it will `SIGSEGV` randomly (when) batch is dropped"]
     fn should_call_stream_leak_memory_1() -> arrow::error::Result<()> {
         let streamer = GraalArrowStreamer::try_new_from_name_and_path("gas", "./target/java")?;
         let mut stream = streamer.create_reader("path")?;
 
         let batch = stream.next();
 
-        // SIGSEGV if streamer is dropped before last
-        // batch is dropped
+        // the reader holds its own clone of the isolate handle, so dropping
+        // the streamer first no longer tears the isolate down underneath it
         drop(streamer);
 
-        // batch drop will call release which should trigger
-        // JNI call. JNI call will SIGSEGV as there is no
-        // Active Isolate (JVM instance)
+        // batch drop triggers the release callback, which is still backed
+        // by the isolate kept alive through `stream`
         drop(batch);
 
         Ok(())
     }
 
+    #[test]
+    fn should_outlive_streamer_with_owned_reader() -> arrow::error::Result<()> {
+        let streamer = GraalArrowStreamer::try_new_from_name_and_path("gas", "./target/java")?;
+        let mut stream = streamer.create_reader_owned("path")?;
+
+        let batch = stream.next().transpose()?;
+
+        // the owned batches hold no reference into the isolate, so dropping
+        // the reader and the streamer (and with them the isolate) first is safe
+        drop(stream);
+        drop(streamer);
+
+        assert!(batch.is_some());
+
+        Ok(())
+    }
+
     #[test]
     #[should_panic = "java.lang.RuntimeException: you've made mock reader panic!"]
     fn should_handle_last_error_message() {
@@ -459,6 +764,23 @@ mod test {
         let _ = streamer.create_reader("panic").unwrap();
     }
 
+    #[test]
+    fn should_fail_create_writer_without_panicking_when_symbol_missing() -> arrow::error::Result<()>
+    {
+        let streamer = GraalArrowStreamer::try_new_from_name_and_path("gas", "./target/java")?;
+        let schema = streamer.create_reader("path")?.schema();
+
+        // the "gas" test fixture predates the write direction and doesn't export
+        // `gas_writer_stream` - `create_writer` must return an `Err` naming the missing symbol
+        // rather than panicking on the `f_create_writer` call
+        let reader = RecordBatchIterator::new(std::iter::empty(), schema);
+        let err = streamer.create_writer("sink", reader).unwrap_err();
+
+        assert!(err.to_string().contains("gas_writer_stream"));
+
+        Ok(())
+    }
+
     #[test]
     fn should_not_close_allocator() -> arrow::error::Result<()> {
         let streamer = GraalArrowStreamer::try_new_from_name_and_path("gas", "./target/java")?;
@@ -475,4 +797,41 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn should_tear_down_without_waiting_on_other_threads() -> arrow::error::Result<()> {
+        let streamer = GraalArrowStreamer::try_new_from_name_and_path("gas", "./target/java")?;
+
+        // the other thread attaches, then parks - keeping its cached attachment alive - until
+        // told to continue, so `drop(streamer)` below races against a thread that is still
+        // running, not one that has already exited and cleaned up after itself
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+        let (continue_tx, continue_rx) = std::sync::mpsc::channel();
+
+        let other_thread_streamer = streamer.clone();
+        let other_thread = std::thread::spawn(move || -> arrow::error::Result<()> {
+            let mut stream = other_thread_streamer.create_reader("path")?;
+            assert!(stream.next().is_some());
+
+            // drop this thread's handles so `drop(streamer)` below is the last reference and
+            // actually tears the isolate down; only the cached attachment stays alive
+            drop(stream);
+            drop(other_thread_streamer);
+
+            ready_tx.send(()).unwrap();
+            continue_rx.recv().unwrap();
+
+            Ok(())
+        });
+
+        ready_rx.recv().unwrap();
+
+        // must return promptly instead of blocking on the still-parked other thread to detach
+        drop(streamer);
+
+        continue_tx.send(()).unwrap();
+        other_thread.join().unwrap()?;
+
+        Ok(())
+    }
 }
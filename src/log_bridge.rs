@@ -0,0 +1,110 @@
+//! Forwards native log lines emitted through `gas_set_log_callback` into the `tracing` facade.
+//!
+//! `gas_last_error` is the only diagnostic channel the native library is guaranteed to expose; the
+//! symbols this module talks to - `gas_log_console_on`/`off`, `gas_set_log_level` and
+//! `gas_set_log_callback` - are all optional, and their absence is not treated as an error.
+
+use std::ffi::c_char;
+use std::os::raw::c_int;
+
+/// Log level accepted by `gas_set_log_level`, mirrored to a `tracing` level when a native log line
+/// is received through [log_trampoline].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub(crate) fn as_c_int(self) -> c_int {
+        match self {
+            LogLevel::Error => 0,
+            LogLevel::Warn => 1,
+            LogLevel::Info => 2,
+            LogLevel::Debug => 3,
+            LogLevel::Trace => 4,
+        }
+    }
+
+    fn from_c_int(level: c_int) -> Self {
+        match level {
+            0 => LogLevel::Error,
+            1 => LogLevel::Warn,
+            3 => LogLevel::Debug,
+            4.. => LogLevel::Trace,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
+/// The trampoline registered with `gas_set_log_callback`.
+///
+/// `msg` is expected to be `len` bytes of (possibly non-NUL-terminated) UTF-8; invalid UTF-8 is
+/// forwarded lossily rather than dropped, so a native logging bug doesn't also hide the message.
+pub(crate) extern "C" fn log_trampoline(level: c_int, msg: *const c_char, len: usize) {
+    if msg.is_null() {
+        return;
+    }
+
+    let bytes = unsafe { std::slice::from_raw_parts(msg as *const u8, len) };
+    let text = String::from_utf8_lossy(bytes);
+
+    match LogLevel::from_c_int(level) {
+        LogLevel::Error => tracing::error!(target: "graalvm_arrow_stream::native", "{text}"),
+        LogLevel::Warn => tracing::warn!(target: "graalvm_arrow_stream::native", "{text}"),
+        LogLevel::Info => tracing::info!(target: "graalvm_arrow_stream::native", "{text}"),
+        LogLevel::Debug => tracing::debug!(target: "graalvm_arrow_stream::native", "{text}"),
+        LogLevel::Trace => tracing::trace!(target: "graalvm_arrow_stream::native", "{text}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_log_levels() {
+        for level in [
+            LogLevel::Error,
+            LogLevel::Warn,
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::Trace,
+        ] {
+            assert_eq!(LogLevel::from_c_int(level.as_c_int()), level);
+        }
+    }
+
+    #[test]
+    fn should_map_negative_levels_to_info_and_high_levels_to_trace() {
+        assert_eq!(LogLevel::from_c_int(-1), LogLevel::Info);
+        assert_eq!(LogLevel::from_c_int(2), LogLevel::Info);
+        assert_eq!(LogLevel::from_c_int(42), LogLevel::Trace);
+    }
+
+    #[test]
+    fn should_not_panic_on_null_message() {
+        log_trampoline(LogLevel::Info.as_c_int(), std::ptr::null(), 0);
+    }
+
+    #[test]
+    fn should_forward_invalid_utf8_lossily() {
+        // a lone continuation byte, not valid UTF-8 on its own
+        let bytes: &[u8] = &[b'o', b'k', 0x80];
+
+        // log_trampoline itself has no return value to assert on - exercise the same lossy
+        // conversion it performs internally directly, since that's the behaviour being tested
+        let text = String::from_utf8_lossy(bytes);
+        assert!(text.starts_with("ok"));
+        assert!(text.contains('\u{FFFD}'));
+
+        log_trampoline(
+            LogLevel::Warn.as_c_int(),
+            bytes.as_ptr() as *const c_char,
+            bytes.len(),
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Helpers for deep-copying Arrow [`ArrayData`] out of GraalVM-owned memory.
+//!
+//! Batches produced by [`crate::LocalArrowArrayStreamReader`] carry Arrow C Data
+//! release callbacks that call back into the isolate that produced them. Once a
+//! batch has been copied with [`deep_copy_record_batch`] it no longer references
+//! any foreign buffer, so it is safe to keep around after the isolate that
+//! produced it has been torn down.
+
+use arrow::array::{ArrayData, RecordBatch};
+use arrow::buffer::Buffer;
+use arrow::error::Result;
+
+/// Deep-copies every column of `batch` into freshly allocated, Rust-owned
+/// buffers, returning a batch with no dependency on the memory it was read
+/// from.
+pub(crate) fn deep_copy_record_batch(batch: &RecordBatch) -> Result<RecordBatch> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|array| Ok(arrow::array::make_array(deep_copy_array_data(&array.to_data()))))
+        .collect::<Result<Vec<_>>>()?;
+
+    RecordBatch::try_new(batch.schema(), columns)
+}
+
+/// Recursively copies an [`ArrayData`] and all of its child data, allocating
+/// new [`Buffer`]s for the data and null buffers along the way.
+fn deep_copy_array_data(data: &ArrayData) -> ArrayData {
+    let buffers: Vec<Buffer> = data
+        .buffers()
+        .iter()
+        .map(|buffer| Buffer::from_slice_ref(buffer.as_slice()))
+        .collect();
+
+    let child_data: Vec<ArrayData> = data.child_data().iter().map(deep_copy_array_data).collect();
+
+    let mut builder = ArrayData::builder(data.data_type().clone())
+        .len(data.len())
+        .offset(data.offset())
+        .buffers(buffers)
+        .child_data(child_data);
+
+    if let Some(nulls) = data.nulls() {
+        let null_buffer = Buffer::from_slice_ref(nulls.buffer().as_slice());
+        builder = builder.null_bit_buffer(Some(null_buffer));
+    }
+
+    // Safety: `data` is already a valid `ArrayData`, we only replaced its
+    // buffers with byte-for-byte copies of the same contents.
+    unsafe { builder.build_unchecked() }
+}
@@ -0,0 +1,147 @@
+//! Per-thread caching of GraalVM isolate thread attachments.
+//!
+//! `graal_attach_thread`/`graal_detach_thread` are comparatively expensive, so a thread that
+//! repeatedly calls into the same isolate (e.g. through [crate::GraalArrowStreamer::create_reader])
+//! should attach once and reuse the resulting `graal_isolatethread_t` rather than attaching and
+//! detaching around every call. Each attachment is cached in a thread-local map and detached when
+//! its owning thread exits - unless [IsolateAttachments::detach_all] reclaims it first, which
+//! happens when the isolate it belongs to is torn down.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread::ThreadId;
+
+use crate::bindings::graal_isolatethread_t;
+use crate::graal::FnDetachThread;
+
+/// Tracks every thread currently attached to one isolate, shared between [crate::GraalIsolate] and
+/// every per-thread cache entry for it.
+///
+/// A thread-local cache entry is keyed by this registry's `Arc` pointer rather than the raw,
+/// GraalVM-owned `graal_isolate_t` pointer, which the native side is free to reuse for a later,
+/// unrelated isolate once this one is torn down. As long as a cache entry is alive it holds this
+/// `Arc` alive too, so its address can never be handed out again for a different isolate - there
+/// is no risk of a stale entry being mistaken for an attachment to that new isolate.
+#[derive(Debug)]
+pub(crate) struct IsolateAttachments {
+    threads: Mutex<HashMap<ThreadId, *mut graal_isolatethread_t>>,
+    f_detach_thread: FnDetachThread,
+}
+
+unsafe impl Send for IsolateAttachments {}
+unsafe impl Sync for IsolateAttachments {}
+
+impl IsolateAttachments {
+    pub(crate) fn new(f_detach_thread: FnDetachThread) -> Arc<Self> {
+        Arc::new(Self {
+            threads: Mutex::new(HashMap::new()),
+            f_detach_thread,
+        })
+    }
+
+    fn register(&self, thread_id: ThreadId, ptr_thread: *mut graal_isolatethread_t) {
+        self.threads.lock().unwrap().insert(thread_id, ptr_thread);
+    }
+
+    fn unregister(&self, thread_id: &ThreadId) -> bool {
+        self.threads.lock().unwrap().remove(thread_id).is_some()
+    }
+
+    /// Detaches every thread still attached to this isolate, wherever they are.
+    ///
+    /// Called when the isolate's last reference drops, so `graal_tear_down_isolate` - which waits
+    /// for every other attached thread to detach - never blocks on a thread that may outlive the
+    /// isolate itself (e.g. a long-lived blocking-pool worker from [crate::AsyncArrowStream]).
+    /// Threads whose own [IsolateThreadGuard] hasn't run yet simply find their entry already gone
+    /// and skip detaching a second time.
+    pub(crate) fn detach_all(&self) {
+        for (_, ptr_thread) in self.threads.lock().unwrap().drain() {
+            unsafe {
+                (self.f_detach_thread)(ptr_thread);
+            }
+        }
+    }
+}
+
+/// A cached thread attachment for one isolate, detached via `f_detach_thread` when dropped -
+/// unless [IsolateAttachments::detach_all] got to it first.
+///
+/// Instances live only inside [ATTACHED_THREADS] and are dropped either when their entry is
+/// explicitly removed or when the thread-local map itself is torn down at thread exit - which is
+/// what gives attachments their "detach on worker thread exit" behaviour.
+struct IsolateThreadGuard {
+    ptr_thread: *mut graal_isolatethread_t,
+    thread_id: ThreadId,
+    attachments: Arc<IsolateAttachments>,
+}
+
+impl Drop for IsolateThreadGuard {
+    fn drop(&mut self) {
+        // `detach_all` may already have removed (and detached) this entry from the isolate's
+        // registry - e.g. because the isolate was torn down while this thread was still running.
+        // Only detach here if we're the one reclaiming it, to avoid detaching it twice.
+        if self.attachments.unregister(&self.thread_id) {
+            unsafe {
+                (self.attachments.f_detach_thread)(self.ptr_thread);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static ATTACHED_THREADS: RefCell<HashMap<usize, IsolateThreadGuard>> = RefCell::new(HashMap::new());
+}
+
+fn cache_key(attachments: &Arc<IsolateAttachments>) -> usize {
+    Arc::as_ptr(attachments) as usize
+}
+
+/// Returns the thread already attached to `attachments` on this thread, if any.
+pub(crate) fn cached_thread(
+    attachments: &Arc<IsolateAttachments>,
+) -> Option<*mut graal_isolatethread_t> {
+    ATTACHED_THREADS.with(|cache| {
+        cache
+            .borrow()
+            .get(&cache_key(attachments))
+            .map(|guard| guard.ptr_thread)
+    })
+}
+
+/// Caches `ptr_thread` as this thread's attachment, registering it with `attachments` so the
+/// isolate can reclaim and detach it proactively if it's torn down before this thread exits.
+pub(crate) fn cache_thread(
+    attachments: &Arc<IsolateAttachments>,
+    ptr_thread: *mut graal_isolatethread_t,
+) {
+    let thread_id = std::thread::current().id();
+    attachments.register(thread_id, ptr_thread);
+
+    ATTACHED_THREADS.with(|cache| {
+        cache.borrow_mut().insert(
+            cache_key(attachments),
+            IsolateThreadGuard {
+                ptr_thread,
+                thread_id,
+                attachments: Arc::clone(attachments),
+            },
+        )
+    });
+}
+
+/// Removes and returns the cached attachment for `attachments` on this thread, if any, without
+/// detaching it - the caller takes over its lifecycle (e.g. to pass it to
+/// `graal_tear_down_isolate`, which itself detaches the thread it is given).
+pub(crate) fn take_cached_thread(
+    attachments: &Arc<IsolateAttachments>,
+) -> Option<*mut graal_isolatethread_t> {
+    ATTACHED_THREADS.with(|cache| {
+        cache.borrow_mut().remove(&cache_key(attachments)).map(|guard| {
+            attachments.unregister(&guard.thread_id);
+            let ptr_thread = guard.ptr_thread;
+            std::mem::forget(guard);
+            ptr_thread
+        })
+    })
+}